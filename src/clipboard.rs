@@ -0,0 +1,302 @@
+use anyhow::{bail, Context, Result};
+use arboard::Clipboard;
+use std::borrow::Cow;
+use std::process::{Command, Stdio};
+use std::io::Write;
+use tracing::{info, warn};
+
+/// Which clipboard a read/write targets.
+///
+/// `Selection` is the X11 "primary selection" (the text highlighted with the
+/// mouse, pasted with a middle click). It has no equivalent on Windows/macOS,
+/// so providers that don't support it should treat it the same as `Clipboard`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClipboardType {
+    Clipboard,
+    Selection,
+}
+
+/// A backend capable of reading and writing clipboard text.
+///
+/// Implementations shell out to whatever clipboard mechanism is actually
+/// available on the running system, since a single library (namely arboard)
+/// can't cover Wayland, headless X11, or SSH sessions with no clipboard
+/// owner at all.
+pub trait ClipboardProvider: Send {
+    fn name(&self) -> Cow<str>;
+    fn get_contents(&self, kind: ClipboardType) -> Result<String>;
+    fn set_contents(&mut self, text: String, kind: ClipboardType) -> Result<()>;
+}
+
+/// The original arboard-backed provider. Works well on X11 and most desktop
+/// setups, but cannot see or set the primary selection and does not work on
+/// Wayland compositors that don't implement the relevant portal.
+pub struct ArboardProvider {
+    clipboard: Clipboard,
+}
+
+impl ArboardProvider {
+    pub fn new() -> Result<Self> {
+        Ok(Self {
+            clipboard: Clipboard::new().context("Failed to initialize arboard clipboard")?,
+        })
+    }
+}
+
+impl ClipboardProvider for ArboardProvider {
+    fn name(&self) -> Cow<str> {
+        Cow::Borrowed("arboard")
+    }
+
+    fn get_contents(&self, kind: ClipboardType) -> Result<String> {
+        warn_if_selection_unsupported(kind);
+        self.clipboard
+            .get_text()
+            .context("Failed to read clipboard via arboard")
+    }
+
+    fn set_contents(&mut self, text: String, kind: ClipboardType) -> Result<()> {
+        warn_if_selection_unsupported(kind);
+        self.clipboard
+            .set_text(&text)
+            .context("Failed to set clipboard via arboard")
+    }
+}
+
+/// arboard has no notion of the X11 primary selection, so a request to
+/// target it silently lands on the regular clipboard instead. Warn so
+/// `--selection` doesn't appear to do nothing without explanation.
+fn warn_if_selection_unsupported(kind: ClipboardType) {
+    if kind == ClipboardType::Selection {
+        warn!("arboard backend does not support the primary selection; using the clipboard instead");
+    }
+}
+
+/// A provider backed by a pair of external command-line tools, one for
+/// reading and one for writing (e.g. `wl-paste`/`wl-copy`). The selection
+/// argument differs per tool, so each concrete constructor below supplies
+/// the right flags for the tool it wraps.
+pub struct CommandProvider {
+    display_name: &'static str,
+    get_cmd: &'static str,
+    get_clipboard_args: &'static [&'static str],
+    get_selection_args: &'static [&'static str],
+    set_cmd: &'static str,
+    set_clipboard_args: &'static [&'static str],
+    set_selection_args: &'static [&'static str],
+}
+
+impl CommandProvider {
+    pub fn wl_clipboard() -> Self {
+        Self {
+            display_name: "wl-clipboard",
+            get_cmd: "wl-paste",
+            get_clipboard_args: &["--no-newline"],
+            get_selection_args: &["--no-newline", "--primary"],
+            set_cmd: "wl-copy",
+            set_clipboard_args: &[],
+            set_selection_args: &["--primary"],
+        }
+    }
+
+    pub fn xclip() -> Self {
+        Self {
+            display_name: "xclip",
+            get_cmd: "xclip",
+            get_clipboard_args: &["-selection", "clipboard", "-o"],
+            get_selection_args: &["-selection", "primary", "-o"],
+            set_cmd: "xclip",
+            set_clipboard_args: &["-selection", "clipboard"],
+            set_selection_args: &["-selection", "primary"],
+        }
+    }
+
+    pub fn xsel() -> Self {
+        Self {
+            display_name: "xsel",
+            get_cmd: "xsel",
+            get_clipboard_args: &["-b", "-o"],
+            get_selection_args: &["-p", "-o"],
+            set_cmd: "xsel",
+            set_clipboard_args: &["-b", "-i"],
+            set_selection_args: &["-p", "-i"],
+        }
+    }
+
+    pub fn macos() -> Self {
+        Self {
+            display_name: "pbcopy/pbpaste",
+            get_cmd: "pbpaste",
+            get_clipboard_args: &[],
+            get_selection_args: &[],
+            set_cmd: "pbcopy",
+            set_clipboard_args: &[],
+            set_selection_args: &[],
+        }
+    }
+
+    pub fn win32yank() -> Self {
+        Self {
+            display_name: "win32yank",
+            get_cmd: "win32yank",
+            get_clipboard_args: &["-o"],
+            get_selection_args: &["-o"],
+            set_cmd: "win32yank",
+            set_clipboard_args: &["-i"],
+            set_selection_args: &["-i"],
+        }
+    }
+
+    /// The name of the binary this provider shells out to, used to probe
+    /// availability with `which` before committing to this provider.
+    pub fn binary_name(&self) -> &'static str {
+        self.get_cmd
+    }
+}
+
+impl ClipboardProvider for CommandProvider {
+    fn name(&self) -> Cow<str> {
+        Cow::Borrowed(self.display_name)
+    }
+
+    fn get_contents(&self, kind: ClipboardType) -> Result<String> {
+        let args = match kind {
+            ClipboardType::Clipboard => self.get_clipboard_args,
+            ClipboardType::Selection => self.get_selection_args,
+        };
+        let output = Command::new(self.get_cmd)
+            .args(args)
+            .output()
+            .with_context(|| format!("Failed to run {}", self.get_cmd))?;
+
+        if !output.status.success() {
+            bail!(
+                "{} exited with {}: {}",
+                self.get_cmd,
+                output.status,
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    }
+
+    fn set_contents(&mut self, text: String, kind: ClipboardType) -> Result<()> {
+        let args = match kind {
+            ClipboardType::Clipboard => self.set_clipboard_args,
+            ClipboardType::Selection => self.set_selection_args,
+        };
+        let mut child = Command::new(self.set_cmd)
+            .args(args)
+            .stdin(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .with_context(|| format!("Failed to spawn {}", self.set_cmd))?;
+
+        child
+            .stdin
+            .take()
+            .context("Failed to open stdin for clipboard command")?
+            .write_all(text.as_bytes())
+            .with_context(|| format!("Failed to write to {}", self.set_cmd))?;
+
+        let output = child
+            .wait_with_output()
+            .with_context(|| format!("Failed to wait for {}", self.set_cmd))?;
+
+        if !output.status.success() {
+            bail!(
+                "{} exited with {}: {}",
+                self.set_cmd,
+                output.status,
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns `true` if `binary` can be found on `$PATH`, used to probe for an
+/// available clipboard backend at startup.
+fn which(binary: &str) -> bool {
+    let lookup_cmd = if cfg!(target_os = "windows") {
+        "where"
+    } else {
+        "which"
+    };
+    Command::new(lookup_cmd)
+        .arg(binary)
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .map(|status| status.success())
+        .unwrap_or(false)
+}
+
+/// Picks the clipboard backend to use, in priority order.
+///
+/// If `forced` names a specific backend it is used (or the attempt fails
+/// loudly), otherwise the session type is probed via `$WAYLAND_DISPLAY` and
+/// `$DISPLAY` and the first available tool for that session is selected,
+/// falling back to arboard if nothing else is found.
+pub fn select_provider(forced: Option<&str>) -> Result<Box<dyn ClipboardProvider>> {
+    if let Some(name) = forced {
+        return build_provider(name);
+    }
+
+    let on_wayland = std::env::var("WAYLAND_DISPLAY").is_ok();
+    let on_x11 = std::env::var("DISPLAY").is_ok();
+
+    let candidates: &[&str] = if cfg!(target_os = "macos") {
+        &["macos", "arboard"]
+    } else if cfg!(target_os = "windows") {
+        &["win32yank", "arboard"]
+    } else if on_wayland {
+        &["wl-clipboard", "xclip", "xsel", "arboard"]
+    } else if on_x11 {
+        &["xclip", "xsel", "arboard"]
+    } else {
+        &["arboard"]
+    };
+
+    for candidate in candidates {
+        if *candidate == "arboard" {
+            if let Ok(provider) = ArboardProvider::new() {
+                info!("Using clipboard backend: arboard");
+                return Ok(Box::new(provider));
+            }
+            continue;
+        }
+
+        let provider = build_command_provider(candidate);
+        if which(provider.binary_name()) {
+            info!("Using clipboard backend: {}", provider.name());
+            return Ok(Box::new(provider));
+        }
+    }
+
+    info!("No external clipboard tool found, falling back to arboard");
+    Ok(Box::new(ArboardProvider::new()?))
+}
+
+fn build_command_provider(name: &str) -> CommandProvider {
+    match name {
+        "wl-clipboard" => CommandProvider::wl_clipboard(),
+        "xclip" => CommandProvider::xclip(),
+        "xsel" => CommandProvider::xsel(),
+        "macos" => CommandProvider::macos(),
+        "win32yank" => CommandProvider::win32yank(),
+        other => unreachable!("unknown built-in clipboard candidate: {}", other),
+    }
+}
+
+fn build_provider(name: &str) -> Result<Box<dyn ClipboardProvider>> {
+    match name {
+        "arboard" => Ok(Box::new(ArboardProvider::new()?)),
+        "wl-clipboard" | "xclip" | "xsel" | "macos" | "win32yank" => {
+            Ok(Box::new(build_command_provider(name)))
+        }
+        other => Err(anyhow::anyhow!("Unknown clipboard backend: {}", other)),
+    }
+}
@@ -0,0 +1,175 @@
+use global_hotkey::hotkey::{Code, Modifiers};
+use std::fmt;
+
+use crate::{Config, HotkeyBinding};
+
+/// Error parsing a hotkey binding out of the config file, carrying the
+/// 1-based line number of the offending TOML line when it could be located.
+#[derive(Debug)]
+pub enum HotkeyParseError {
+    InvalidModifier {
+        line: Option<usize>,
+        modifier: String,
+    },
+    InvalidKeysym {
+        line: Option<usize>,
+        key: String,
+    },
+    MissingKey {
+        line: Option<usize>,
+        action: String,
+    },
+}
+
+impl fmt::Display for HotkeyParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HotkeyParseError::InvalidModifier { line, modifier } => {
+                write!(f, "unknown hotkey modifier '{}'{}", modifier, at_line(*line))
+            }
+            HotkeyParseError::InvalidKeysym { line, key } => {
+                write!(f, "unknown hotkey key '{}'{}", key, at_line(*line))
+            }
+            HotkeyParseError::MissingKey { line, action } => {
+                write!(f, "hotkey for '{}' has no key bound{}", action, at_line(*line))
+            }
+        }
+    }
+}
+
+impl std::error::Error for HotkeyParseError {}
+
+fn at_line(line: Option<usize>) -> String {
+    match line {
+        Some(n) => format!(" (config line {})", n),
+        None => String::new(),
+    }
+}
+
+/// Finds the 1-based line number of `field = ...` inside the `[table_path]`
+/// section of a raw TOML document, so a parse error can point the user at
+/// the exact line instead of just naming the action.
+fn find_line(raw_toml: &str, table_path: &str, field: &str) -> Option<usize> {
+    let header = format!("[{}]", table_path);
+    let mut in_table = false;
+
+    for (idx, line) in raw_toml.lines().enumerate() {
+        let trimmed = line.trim();
+        if trimmed.starts_with('[') {
+            in_table = trimmed == header;
+            continue;
+        }
+        if in_table && trimmed.starts_with(field) {
+            let after = trimmed[field.len()..].trim_start();
+            if after.starts_with('=') {
+                return Some(idx + 1);
+            }
+        }
+    }
+
+    None
+}
+
+pub fn modifiers_from(names: &[String], line: Option<usize>) -> Result<Modifiers, HotkeyParseError> {
+    let mut modifiers = Modifiers::empty();
+    for modifier in names {
+        match modifier.to_uppercase().as_str() {
+            "CMD" | "WIN" | "META" => modifiers |= Modifiers::SUPER,
+            "CTRL" | "CONTROL" => modifiers |= Modifiers::CONTROL,
+            "ALT" | "OPTION" => modifiers |= Modifiers::ALT,
+            "SHIFT" => modifiers |= Modifiers::SHIFT,
+            _ => {
+                return Err(HotkeyParseError::InvalidModifier {
+                    line,
+                    modifier: modifier.clone(),
+                })
+            }
+        }
+    }
+    Ok(modifiers)
+}
+
+pub fn code_from_key(key: &str, line: Option<usize>) -> Result<Code, HotkeyParseError> {
+    let code = match key.to_uppercase().as_str() {
+        "A" => Code::KeyA, "B" => Code::KeyB, "C" => Code::KeyC, "D" => Code::KeyD,
+        "E" => Code::KeyE, "F" => Code::KeyF, "G" => Code::KeyG, "H" => Code::KeyH,
+        "I" => Code::KeyI, "J" => Code::KeyJ, "K" => Code::KeyK, "L" => Code::KeyL,
+        "M" => Code::KeyM, "N" => Code::KeyN, "O" => Code::KeyO, "P" => Code::KeyP,
+        "Q" => Code::KeyQ, "R" => Code::KeyR, "S" => Code::KeyS, "T" => Code::KeyT,
+        "U" => Code::KeyU, "V" => Code::KeyV, "W" => Code::KeyW, "X" => Code::KeyX,
+        "Y" => Code::KeyY, "Z" => Code::KeyZ,
+        "1" => Code::Digit1, "2" => Code::Digit2, "3" => Code::Digit3,
+        "4" => Code::Digit4, "5" => Code::Digit5, "6" => Code::Digit6,
+        "7" => Code::Digit7, "8" => Code::Digit8, "9" => Code::Digit9, "0" => Code::Digit0,
+        "SPACE" => Code::Space,
+        "ENTER" | "RETURN" => Code::Enter,
+        "TAB" => Code::Tab,
+        "ESC" | "ESCAPE" => Code::Escape,
+        "BACKSPACE" => Code::Backspace,
+        "DELETE" | "DEL" => Code::Delete,
+        "HOME" => Code::Home,
+        "END" => Code::End,
+        "PAGEUP" => Code::PageUp,
+        "PAGEDOWN" => Code::PageDown,
+        "UP" | "ARROWUP" => Code::ArrowUp,
+        "DOWN" | "ARROWDOWN" => Code::ArrowDown,
+        "LEFT" | "ARROWLEFT" => Code::ArrowLeft,
+        "RIGHT" | "ARROWRIGHT" => Code::ArrowRight,
+        "F1" => Code::F1, "F2" => Code::F2, "F3" => Code::F3, "F4" => Code::F4,
+        "F5" => Code::F5, "F6" => Code::F6, "F7" => Code::F7, "F8" => Code::F8,
+        "F9" => Code::F9, "F10" => Code::F10, "F11" => Code::F11, "F12" => Code::F12,
+        "F13" => Code::F13, "F14" => Code::F14, "F15" => Code::F15, "F16" => Code::F16,
+        "F17" => Code::F17, "F18" => Code::F18, "F19" => Code::F19, "F20" => Code::F20,
+        "F21" => Code::F21, "F22" => Code::F22, "F23" => Code::F23, "F24" => Code::F24,
+        "COMMA" | "," => Code::Comma,
+        "PERIOD" | "." => Code::Period,
+        "SLASH" | "/" => Code::Slash,
+        "SEMICOLON" | ";" => Code::Semicolon,
+        "QUOTE" | "'" => Code::Quote,
+        "LBRACKET" | "[" => Code::BracketLeft,
+        "RBRACKET" | "]" => Code::BracketRight,
+        "BACKSLASH" | "\\" => Code::Backslash,
+        "MINUS" | "-" => Code::Minus,
+        "EQUAL" | "=" => Code::Equal,
+        "BACKQUOTE" | "`" => Code::Backquote,
+        _ => {
+            return Err(HotkeyParseError::InvalidKeysym {
+                line,
+                key: key.to_string(),
+            })
+        }
+    };
+    Ok(code)
+}
+
+/// Validates every configured hotkey binding against `raw_toml`, so that a
+/// bad modifier or keysym is reported with the config line it came from
+/// instead of just the action name.
+pub fn validate_hotkeys(config: &Config, raw_toml: &str) -> Result<(), HotkeyParseError> {
+    let next_binding = Some(config.hotkeys.next.clone());
+    let bindings: [(&str, &Option<HotkeyBinding>); 4] = [
+        ("hotkeys.next", &next_binding),
+        ("hotkeys.previous", &config.hotkeys.previous),
+        ("hotkeys.reset", &config.hotkeys.reset),
+        ("hotkeys.skip", &config.hotkeys.skip),
+    ];
+
+    for (table_path, binding) in bindings {
+        let Some(binding) = binding else { continue };
+
+        if binding.key.trim().is_empty() {
+            return Err(HotkeyParseError::MissingKey {
+                line: find_line(raw_toml, table_path, "key"),
+                action: table_path.to_string(),
+            });
+        }
+
+        let modifier_line = find_line(raw_toml, table_path, "modifiers");
+        modifiers_from(&binding.modifiers, modifier_line)?;
+
+        let key_line = find_line(raw_toml, table_path, "key");
+        code_from_key(&binding.key, key_line)?;
+    }
+
+    Ok(())
+}
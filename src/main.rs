@@ -1,13 +1,13 @@
 use anyhow::{Context, Result};
-use arboard::Clipboard;
 use clap::Parser;
 use enigo::{Enigo, Key, Direction::{Press, Release, Click}, Settings, Keyboard};
 use global_hotkey::{
-    hotkey::{Code, HotKey, Modifiers},
+    hotkey::HotKey,
     GlobalHotKeyEvent, GlobalHotKeyManager, HotKeyState,
 };
 use serde::{Deserialize, Serialize};
 use std::{
+    collections::HashMap,
     fs,
     io::{self, Write},
     path::PathBuf,
@@ -18,9 +18,14 @@ use std::{
     thread,
     time::Duration,
 };
-use tracing::{error, info, warn};
+use tracing::{error, info};
 use winit::event_loop::{ControlFlow, EventLoop};
 
+mod clipboard;
+mod hotkey_config;
+
+use clipboard::{ClipboardProvider, ClipboardType};
+
 #[derive(Parser, Debug)]
 #[command(name = "easypaste")]
 #[command(about = "A cross-platform clipboard automation tool")]
@@ -44,15 +49,104 @@ struct Args {
     /// Enable verbose logging (info level)
     #[arg(short, long)]
     verbose: bool,
+
+    /// Force a specific clipboard backend instead of auto-detecting one
+    /// (arboard, wl-clipboard, xclip, xsel, macos, win32yank)
+    #[arg(long)]
+    clipboard_backend: Option<String>,
+
+    /// Write triggered segments to the X11 primary selection instead of the clipboard
+    #[arg(long)]
+    selection: bool,
+
+    /// How to deliver a segment to the focused window: `clipboard` (Ctrl/Cmd+V)
+    /// or `typeout` (simulate keystrokes, for apps that mangle pasted text)
+    #[arg(long)]
+    paste_mode: Option<PasteMode>,
+
+    /// Delay between simulated keystrokes in typeout mode, in milliseconds
+    #[arg(long)]
+    typeout_delay_ms: Option<u64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum PasteMode {
+    /// Copy to the clipboard and press the platform paste chord (default).
+    #[default]
+    Clipboard,
+    /// Copy to the clipboard, then type the segment out character by character.
+    Typeout,
+}
+
+/// A single hotkey binding, expressed as the same modifier names and key
+/// name accepted everywhere else in the config (e.g. `["CTRL", "SHIFT"]` + `"B"`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct HotkeyBinding {
+    pub(crate) modifiers: Vec<String>,
+    pub(crate) key: String,
+}
+
+/// The four navigation actions a hotkey can be bound to. `next` is always
+/// bound; the others are optional so a user who only wants forward
+/// navigation doesn't have to configure anything extra.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct HotkeysConfig {
+    pub(crate) next: HotkeyBinding,
+    #[serde(default)]
+    pub(crate) previous: Option<HotkeyBinding>,
+    #[serde(default)]
+    pub(crate) reset: Option<HotkeyBinding>,
+    #[serde(default)]
+    pub(crate) skip: Option<HotkeyBinding>,
+}
+
+impl Default for HotkeysConfig {
+    fn default() -> Self {
+        Self {
+            next: HotkeyBinding {
+                modifiers: vec!["CTRL".to_string(), "SHIFT".to_string()],
+                key: "B".to_string(),
+            },
+            previous: Some(HotkeyBinding {
+                modifiers: vec!["CTRL".to_string(), "SHIFT".to_string()],
+                key: "Z".to_string(),
+            }),
+            reset: Some(HotkeyBinding {
+                modifiers: vec!["CTRL".to_string(), "SHIFT".to_string()],
+                key: "R".to_string(),
+            }),
+            skip: Some(HotkeyBinding {
+                modifiers: vec!["CTRL".to_string(), "SHIFT".to_string()],
+                key: "N".to_string(),
+            }),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 struct Config {
     delimiter: String,
     file_path: PathBuf,
-    hotkey_modifiers: Vec<String>,
-    hotkey_key: String,
+    #[serde(default)]
+    pub(crate) hotkeys: HotkeysConfig,
     paste: Option<bool>,
+    /// Force a specific clipboard backend; `None` means auto-detect.
+    #[serde(default)]
+    clipboard_backend: Option<String>,
+    /// Write triggered segments to the X11 primary selection instead of the clipboard.
+    #[serde(default)]
+    selection: bool,
+    /// How a segment is delivered to the focused window.
+    #[serde(default)]
+    paste_mode: PasteMode,
+    /// Delay between simulated keystrokes when `paste_mode` is `typeout`.
+    #[serde(default = "default_typeout_delay_ms")]
+    typeout_delay_ms: u64,
+}
+
+fn default_typeout_delay_ms() -> u64 {
+    10
 }
 
 impl Default for Config {
@@ -60,9 +154,12 @@ impl Default for Config {
         Self {
             delimiter: "%%%".to_string(),
             file_path: PathBuf::from("input.txt"),
-            hotkey_modifiers: vec!["CTRL".to_string(), "SHIFT".to_string()],
-            hotkey_key: "B".to_string(),
+            hotkeys: HotkeysConfig::default(),
             paste: Some(true),
+            clipboard_backend: None,
+            selection: false,
+            paste_mode: PasteMode::Clipboard,
+            typeout_delay_ms: default_typeout_delay_ms(),
         }
     }
 }
@@ -72,7 +169,7 @@ const DONATE_LINK: &str = "https://donate.stripe.com/8x28wObdhgoV8aVaQW6J202";
 fn show_donation_prompt() {
     print!("\nDo you like the tool and want to buy me a coffee? [y/N]: ");
     io::stdout().flush().unwrap();
-    
+
     let mut input = String::new();
     if io::stdin().read_line(&mut input).is_ok() {
         if input.trim().to_lowercase() == "y" {
@@ -83,10 +180,27 @@ fn show_donation_prompt() {
     }
 }
 
+/// Byte-offset span of one segment within the loaded file, plus the
+/// optional note trailing its delimiter.
+#[derive(Debug, Clone)]
+struct Segment {
+    start: usize,
+    end: usize,
+    note: Option<String>,
+}
+
+/// Holds the file content parsed into segments exactly once, with a cursor
+/// that can advance, skip, or reset instead of re-scanning the delimiter
+/// from the current byte position on every call. Going back is served from
+/// the delivery history, not by moving the cursor.
 struct TextManager {
-    content: Arc<Mutex<String>>,
-    position: Arc<AtomicUsize>,
-    delimiter: String,
+    content: String,
+    segments: Vec<Segment>,
+    cursor: AtomicUsize,
+    /// Indices actually delivered via `next`/`previous`, in delivery order.
+    /// `skip` does not append here, so `previous` always means "the segment
+    /// delivered before the most recent one" regardless of intervening skips.
+    delivered: Mutex<Vec<usize>>,
 }
 
 impl TextManager {
@@ -94,120 +208,281 @@ impl TextManager {
         let content = fs::read_to_string(&file_path)
             .with_context(|| format!("Failed to read file: {}", file_path.display()))?;
 
+        let segments = Self::parse_segments(&content, &delimiter);
+
         Ok(Self {
-            content: Arc::new(Mutex::new(content)),
-            position: Arc::new(AtomicUsize::new(0)),
-            delimiter,
+            content,
+            segments,
+            cursor: AtomicUsize::new(0),
+            delivered: Mutex::new(Vec::new()),
         })
     }
 
-    fn get_next_segment(&self) -> Option<(String, Option<String>)> {
-        let content = self.content.lock().unwrap();
-        let current_pos = self.position.load(Ordering::Relaxed);
-
-        if current_pos >= content.len() {
-            // Don't reset, just return None to indicate we're done
-            if content.is_empty() {
-                return None;
-            }
-            return None;
-        }
-
-        let remaining = &content[current_pos..];
-        
-        match remaining.find(&self.delimiter) {
-            Some(delimiter_pos) => {
-                let segment = remaining[..delimiter_pos].to_string();
-                
-                // Check if there's an internal note on the same line after the delimiter
-                let after_delimiter = &remaining[delimiter_pos + self.delimiter.len()..];
-                let internal_note = if let Some(newline_pos) = after_delimiter.find('\n') {
-                    if newline_pos > 0 {
-                        Some(after_delimiter[..newline_pos].trim().to_string())
-                    } else {
-                        None
-                    }
-                } else if !after_delimiter.is_empty() {
-                    Some(after_delimiter.trim().to_string())
-                } else {
-                    None
-                };
-                
-                // Move position past the delimiter, any internal note, and the newline
-                let after_note = &remaining[delimiter_pos + self.delimiter.len()..];
-                if let Some(newline_pos) = after_note.find('\n') {
-                    self.position.store(current_pos + delimiter_pos + self.delimiter.len() + newline_pos + 1, Ordering::Relaxed);
-                } else {
-                    self.position.store(current_pos + delimiter_pos + self.delimiter.len() + after_note.len(), Ordering::Relaxed);
-                }
-                
-                Some((segment, internal_note))
-            }
-            None => {
-                // No more delimiters, return rest of content
-                if !remaining.is_empty() {
-                    self.position.store(content.len(), Ordering::Relaxed);
-                    Some((remaining.to_string(), None))
-                } else {
-                    None
-                }
-            }
+    #[cfg(test)]
+    fn from_content(content: &str, delimiter: &str) -> Self {
+        Self {
+            content: content.to_string(),
+            segments: Self::parse_segments(content, delimiter),
+            cursor: AtomicUsize::new(0),
+            delivered: Mutex::new(Vec::new()),
         }
     }
 
-    fn preview_next_segment(&self) -> Option<(String, Option<String>)> {
-        let content = self.content.lock().unwrap();
-        let current_pos = self.position.load(Ordering::Relaxed);
+    /// Splits `content` into segment spans on `delimiter`, extracting the
+    /// optional inline note that trails a delimiter on the same line.
+    fn parse_segments(content: &str, delimiter: &str) -> Vec<Segment> {
+        let mut segments = Vec::new();
+        let mut pos = 0;
 
-        if current_pos >= content.len() {
-            // If we're at the end, there are no more segments
-            return None;
-        } else {
-            let remaining = &content[current_pos..];
-            match remaining.find(&self.delimiter) {
+        while pos < content.len() {
+            let remaining = &content[pos..];
+            match remaining.find(delimiter) {
                 Some(delimiter_pos) => {
-                    let segment = remaining[..delimiter_pos].to_string();
-                    
-                    // Check for internal note
-                    let after_delimiter = &remaining[delimiter_pos + self.delimiter.len()..];
-                    let internal_note = if let Some(newline_pos) = after_delimiter.find('\n') {
-                        if newline_pos > 0 {
+                    let segment_end = pos + delimiter_pos;
+                    let after_delimiter = &remaining[delimiter_pos + delimiter.len()..];
+
+                    let (note, consumed) = if let Some(newline_pos) = after_delimiter.find('\n') {
+                        let note = if newline_pos > 0 {
                             Some(after_delimiter[..newline_pos].trim().to_string())
                         } else {
                             None
-                        }
-                    } else if !after_delimiter.is_empty() {
-                        Some(after_delimiter.trim().to_string())
+                        };
+                        (note, newline_pos + 1)
                     } else {
-                        None
+                        let note = if !after_delimiter.is_empty() {
+                            Some(after_delimiter.trim().to_string())
+                        } else {
+                            None
+                        };
+                        (note, after_delimiter.len())
                     };
-                    
-                    Some((segment, internal_note))
-                },
+
+                    segments.push(Segment {
+                        start: pos,
+                        end: segment_end,
+                        note,
+                    });
+
+                    pos = segment_end + delimiter.len() + consumed;
+                }
                 None => {
-                    if !remaining.is_empty() {
-                        Some((remaining.to_string(), None))
-                    } else {
-                        None
-                    }
+                    segments.push(Segment {
+                        start: pos,
+                        end: content.len(),
+                        note: None,
+                    });
+                    break;
                 }
             }
         }
+
+        segments
+    }
+
+    fn segment_text(&self, segment: &Segment) -> (String, Option<String>) {
+        (
+            self.content[segment.start..segment.end].to_string(),
+            segment.note.clone(),
+        )
+    }
+
+    fn peek_at(&self, index: usize) -> Option<(String, Option<String>)> {
+        self.segments.get(index).map(|s| self.segment_text(s))
+    }
+
+    /// Returns the segment the cursor currently points at without moving it.
+    fn peek(&self) -> Option<(String, Option<String>)> {
+        self.peek_at(self.cursor.load(Ordering::Relaxed))
+    }
+
+    /// Delivers the segment the cursor points at and advances it.
+    fn next(&self) -> Option<(String, Option<String>)> {
+        let index = self.cursor.load(Ordering::Relaxed);
+        let segment = self.peek_at(index)?;
+        self.cursor.store(index + 1, Ordering::Relaxed);
+        self.delivered.lock().unwrap().push(index);
+        Some(segment)
+    }
+
+    /// Re-delivers the segment delivered before the most recent one, so a
+    /// misfire can be undone. Returns `None` (delivering nothing) if fewer
+    /// than two segments have been delivered yet. Tracked via `delivered`
+    /// rather than the cursor, since `skip` moves the cursor without being
+    /// a delivery and must not shift what "previous" means.
+    fn previous(&self) -> Option<(String, Option<String>)> {
+        let mut delivered = self.delivered.lock().unwrap();
+        if delivered.len() < 2 {
+            return None;
+        }
+        let target = delivered[delivered.len() - 2];
+        let segment = self.peek_at(target)?;
+        delivered.push(target);
+        Some(segment)
+    }
+
+    /// Moves the cursor back to the first segment and clears delivery
+    /// history, without delivering anything.
+    fn reset(&self) {
+        self.cursor.store(0, Ordering::Relaxed);
+        self.delivered.lock().unwrap().clear();
+    }
+
+    /// Advances past the segment the cursor points at without delivering it.
+    /// Returns `true` if there was a segment to skip.
+    fn skip(&self) -> bool {
+        let index = self.cursor.load(Ordering::Relaxed);
+        if index < self.segments.len() {
+            self.cursor.store(index + 1, Ordering::Relaxed);
+            true
+        } else {
+            false
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn previous_after_skip_redelivers_segment_before_last_delivered() {
+        let tm = TextManager::from_content("one%%%\ntwo%%%\nthree%%%\nfour", "%%%");
+
+        assert_eq!(tm.next().unwrap().0, "one");
+        assert_eq!(tm.next().unwrap().0, "two");
+        assert!(tm.skip()); // skips "three" without delivering it
+
+        let (segment, _) = tm.previous().expect("previous should still undo to \"one\"");
+        assert_eq!(segment, "one");
+    }
+}
+
+fn print_preview(preview: Option<(String, Option<String>)>) {
+    if let Some((segment, note)) = preview {
+        if !segment.is_empty() {
+            println!("Next segment preview:");
+            println!("{}", segment);
+            if let Some(note_text) = note {
+                println!("[Note: {}]", note_text);
+            }
+            println!("---");
+        }
+    }
+}
+
+/// The navigation action a fired hotkey maps to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Action {
+    Next,
+    Previous,
+    Reset,
+    Skip,
+}
+
+fn build_hotkey(binding: &HotkeyBinding) -> Result<HotKey> {
+    let modifiers = hotkey_config::modifiers_from(&binding.modifiers, None)?;
+    let code = hotkey_config::code_from_key(&binding.key, None)?;
+    Ok(HotKey::new(Some(modifiers), code))
+}
+
+fn paste_active_segment() {
+    if let Ok(mut enigo) = Enigo::new(&Settings::default()) {
+        // Small delay to ensure clipboard is set
+        let sleep_duration = if cfg!(target_os = "macos") {
+            100
+        } else if cfg!(target_os = "windows") {
+            2000
+        } else {
+            100 // Default for other platforms
+        };
+        thread::sleep(Duration::from_millis(sleep_duration));
+
+        // Use appropriate modifier key based on platform
+        let modifier_key = if cfg!(target_os = "macos") {
+            Key::Meta
+        } else {
+            Key::Control
+        };
+
+        if let Err(e) = enigo.key(modifier_key, Press) {
+            error!("Failed to press modifier key: {}", e);
+        } else if let Err(e) = enigo.key(Key::Unicode('v'), Click) {
+            error!("Failed to click V key: {}", e);
+        } else if let Err(e) = enigo.key(modifier_key, Release) {
+            error!("Failed to release modifier key: {}", e);
+        } else {
+            info!("Pasted clipboard contents");
+        }
+    } else {
+        error!("Failed to initialize Enigo for pasting");
+    }
+}
+
+/// Simulates typing `segment` one character at a time, for target
+/// applications (terminals, remote-desktop windows, some web forms) that
+/// ignore or mangle a clipboard paste.
+fn type_out_segment(segment: &str, delay_ms: u64) {
+    let Ok(mut enigo) = Enigo::new(&Settings::default()) else {
+        error!("Failed to initialize Enigo for typeout");
+        return;
+    };
+
+    let delay = Duration::from_millis(delay_ms);
+    for ch in segment.chars() {
+        if let Err(e) = enigo.key(Key::Unicode(ch), Click) {
+            error!("Failed to type character '{}': {}", ch, e);
+            return;
+        }
+        thread::sleep(delay);
+    }
+
+    info!("Typed out segment: {:.50}...", segment);
+}
+
+/// Copies `segment` to the clipboard and, if auto-paste is enabled,
+/// delivers it to the focused window per `paste_mode`. Shared by the
+/// `next` and `previous` actions, which are the only ones that hand the
+/// user new clipboard content.
+fn deliver_segment(
+    segment: &str,
+    clipboard: &Arc<Mutex<Box<dyn ClipboardProvider>>>,
+    clipboard_type: ClipboardType,
+    should_paste: bool,
+    paste_mode: PasteMode,
+    typeout_delay_ms: u64,
+) {
+    if segment.is_empty() {
+        return;
+    }
+    if let Ok(mut cb) = clipboard.lock() {
+        if let Err(e) = cb.set_contents(segment.to_string(), clipboard_type) {
+            error!("Failed to set clipboard: {}", e);
+            return;
+        }
+        info!("Set clipboard to: {:.50}...", segment);
+        drop(cb);
+
+        if should_paste {
+            match paste_mode {
+                PasteMode::Clipboard => paste_active_segment(),
+                PasteMode::Typeout => type_out_segment(segment, typeout_delay_ms),
+            }
+        }
     }
 }
 
 struct EasypasteApp {
     text_manager: Arc<TextManager>,
-        clipboard: Arc<Mutex<Clipboard>>,
+        clipboard: Arc<Mutex<Box<dyn ClipboardProvider>>>,
         hotkey_manager: Arc<Mutex<GlobalHotKeyManager>>,
 }
 
 impl EasypasteApp {
     fn new(config: Config) -> Result<Self> {
         let text_manager = Arc::new(TextManager::new(config.file_path, config.delimiter)?);
-        let clipboard = Arc::new(Mutex::new(
-            Clipboard::new().context("Failed to initialize clipboard")?,
-        ));
+        let clipboard = Arc::new(Mutex::new(clipboard::select_provider(
+            config.clipboard_backend.as_deref(),
+        )?));
 
         let hotkey_manager = GlobalHotKeyManager::new()
             .context("Failed to initialize global hotkey manager")?;
@@ -219,86 +494,77 @@ impl EasypasteApp {
         })
     }
 
-    fn register_hotkey(&self, config: &Config) -> Result<HotKey> {
-        let mut modifiers = Modifiers::empty();
-        for modifier in &config.hotkey_modifiers {
-            match modifier.to_uppercase().as_str() {
-                "CMD" | "WIN" | "META" => modifiers |= Modifiers::SUPER,
-                "CTRL" | "CONTROL" => modifiers |= Modifiers::CONTROL,
-                "ALT" | "OPTION" => modifiers |= Modifiers::ALT,
-                "SHIFT" => modifiers |= Modifiers::SHIFT,
-                _ => warn!("Unknown modifier: {}", modifier),
-            }
-        }
-
-        let key_code = match config.hotkey_key.to_uppercase().as_str() {
-            "A" => Code::KeyA, "B" => Code::KeyB, "C" => Code::KeyC, "D" => Code::KeyD,
-            "E" => Code::KeyE, "F" => Code::KeyF, "G" => Code::KeyG, "H" => Code::KeyH,
-            "I" => Code::KeyI, "J" => Code::KeyJ, "K" => Code::KeyK, "L" => Code::KeyL,
-            "M" => Code::KeyM, "N" => Code::KeyN, "O" => Code::KeyO, "P" => Code::KeyP,
-            "Q" => Code::KeyQ, "R" => Code::KeyR, "S" => Code::KeyS, "T" => Code::KeyT,
-            "U" => Code::KeyU, "V" => Code::KeyV, "W" => Code::KeyW, "X" => Code::KeyX,
-            "Y" => Code::KeyY, "Z" => Code::KeyZ,
-            "1" => Code::Digit1, "2" => Code::Digit2, "3" => Code::Digit3,
-            "4" => Code::Digit4, "5" => Code::Digit5, "6" => Code::Digit6,
-            "7" => Code::Digit7, "8" => Code::Digit8, "9" => Code::Digit9, "0" => Code::Digit0,
-            "SPACE" => Code::Space,
-            "ENTER" | "RETURN" => Code::Enter,
-            _ => {
-                return Err(anyhow::anyhow!("Unsupported key: {}", config.hotkey_key));
-            }
-        };
+    /// Registers every bound hotkey and returns the hotkeys so callers can
+    /// dispatch on `hotkey_event.id()` and unregister them on shutdown.
+    fn register_hotkeys(&self, config: &Config) -> Result<Vec<(HotKey, Action)>> {
+        let mut manager = self.hotkey_manager.lock().unwrap();
+        let mut registered = Vec::new();
 
-        let hotkey = HotKey::new(Some(modifiers), key_code);
-        self.hotkey_manager
-            .lock()
-            .unwrap()
-            .register(hotkey)
-            .with_context(|| {
+        let mut register = |binding: &HotkeyBinding, action: Action| -> Result<()> {
+            let hotkey = build_hotkey(binding)?;
+            manager.register(hotkey).with_context(|| {
                 format!(
-                    "Failed to register hotkey: {:?}+{}",
-                    modifiers, config.hotkey_key
+                    "Failed to register {:?} hotkey: {}+{}",
+                    action,
+                    binding.modifiers.join("+"),
+                    binding.key
                 )
             })?;
+            println!(
+                "Registered {:?} hotkey: {}+{}",
+                action,
+                binding.modifiers.join("+"),
+                binding.key
+            );
+            registered.push((hotkey, action));
+            Ok(())
+        };
 
-            println!("Registered hotkey: {:?}+{}", modifiers, config.hotkey_key);
-        Ok(hotkey)
-    }
-
+        register(&config.hotkeys.next, Action::Next)?;
+        if let Some(binding) = &config.hotkeys.previous {
+            register(binding, Action::Previous)?;
+        }
+        if let Some(binding) = &config.hotkeys.reset {
+            register(binding, Action::Reset)?;
+        }
+        if let Some(binding) = &config.hotkeys.skip {
+            register(binding, Action::Skip)?;
+        }
 
+        Ok(registered)
+    }
 
     fn run(&self, config: Config) -> Result<()> {
-        let hotkey = self.register_hotkey(&config)?;
+        let hotkeys = self.register_hotkeys(&config)?;
+        let action_by_id: HashMap<u32, Action> = hotkeys
+            .iter()
+            .map(|(hotkey, action)| (hotkey.id(), *action))
+            .collect();
 
-        println!("Easypaste is running. Press the configured hotkey to paste next segment.");
+        println!("Easypaste is running. Press the configured hotkeys to navigate segments.");
         println!("File: {}", config.file_path.display());
         println!("Delimiter: '{}'", config.delimiter);
         println!("Auto-paste: {}", config.paste.unwrap_or(true));
         println!("Press Ctrl+C to exit");
 
-        // Show initial preview
-        if let Some((segment, note)) = self.text_manager.preview_next_segment() {
-            if !segment.is_empty() {
-                println!("Next segment preview:");
-                println!("{}", segment);
-                if let Some(note_text) = note {
-                    println!("[Note: {}]", note_text);
-                }
-                println!("---");
-            }
-        }
+        print_preview(self.text_manager.peek());
 
         // Create event loop (required for hotkey system integration)
         let event_loop = EventLoop::new().context("Failed to create event loop")?;
-        
+
         let text_manager = Arc::clone(&self.text_manager);
         let clipboard = Arc::clone(&self.clipboard);
         let should_exit = Arc::new(Mutex::new(false));
+        let clipboard_type = if config.selection {
+            ClipboardType::Selection
+        } else {
+            ClipboardType::Clipboard
+        };
 
         // Set up event loop
         event_loop.run(move |event, elwt| {
             elwt.set_control_flow(ControlFlow::Wait);
-            
+
             match event {
                 winit::event::Event::WindowEvent { event, .. } => match event {
                     winit::event::WindowEvent::CloseRequested => {
@@ -309,97 +575,84 @@ impl EasypasteApp {
                 },
                 _ => {}
             }
-            
+
             // Handle global hotkey events
             if let Ok(hotkey_event) = GlobalHotKeyEvent::receiver().try_recv() {
                 // Check if we should exit before processing hotkey events
                 if *should_exit.lock().unwrap() {
                     return;
                 }
-                
+
                 // Only react to key press events, not key release events
-                if hotkey_event.state == HotKeyState::Pressed {
-                    info!("Hotkey triggered: {:?}", hotkey_event);
-                    
-                    // Get and copy the actual segment
-                    if let Some((segment, _)) = text_manager.get_next_segment() {
-                        if !segment.is_empty() {
-                            if let Ok(mut cb) = clipboard.lock() {
-                                if let Err(e) = cb.set_text(&segment) {
-                                    error!("Failed to set clipboard: {}", e);
-                                } else {
-                                    info!("Set clipboard to: {:.50}...", segment);
-                                    
-                                    // Paste the contents if enabled
-                                    if config.paste.unwrap_or(true) {
-                                        // We need to drop the clipboard lock before trying to paste
-                                        drop(cb);
-                                        
-                                        // Create a new Enigo instance for pasting
-                                        if let Ok(mut enigo) = Enigo::new(&Settings::default()) {
-                                            // Small delay to ensure clipboard is set
-                                            let sleep_duration = if cfg!(target_os = "macos") {
-                                                100
-                                            } else if cfg!(target_os = "windows") {
-                                                2000
-                                            } else {
-                                                100 // Default for other platforms
-                                            };
-                                            thread::sleep(Duration::from_millis(sleep_duration));
-                                            
-                                            // Use appropriate modifier key based on platform
-                                            let modifier_key = if cfg!(target_os = "macos") {
-                                                Key::Meta
-                                            } else {
-                                                Key::Control
-                                            };
-                                            
-                                            if let Err(e) = enigo.key(modifier_key, Press) {
-                                                error!("Failed to press modifier key: {}", e);
-                                            } else if let Err(e) = enigo.key(Key::Unicode('v'), Click) {
-                                                error!("Failed to click V key: {}", e);
-                                            } else if let Err(e) = enigo.key(modifier_key, Release) {
-                                                error!("Failed to release modifier key: {}", e);
-                                            } else {
-                                                info!("Pasted clipboard contents");
-                                            }
-                                        } else {
-                                            error!("Failed to initialize Enigo for pasting");
-                                        }
-                                    }
+                if hotkey_event.state != HotKeyState::Pressed {
+                    return;
+                }
+
+                let Some(action) = action_by_id.get(&hotkey_event.id()).copied() else {
+                    return;
+                };
+                info!("Hotkey triggered: {:?}", action);
+
+                match action {
+                    Action::Next => match text_manager.next() {
+                        Some((segment, _)) => {
+                            deliver_segment(
+                                &segment,
+                                &clipboard,
+                                clipboard_type,
+                                config.paste.unwrap_or(true),
+                                config.paste_mode,
+                                config.typeout_delay_ms,
+                            );
+                            match text_manager.peek() {
+                                Some(preview) => print_preview(Some(preview)),
+                                None => {
+                                    info!("All segments processed. Exiting...");
+                                    show_donation_prompt();
+                                    std::process::exit(0);
                                 }
                             }
                         }
-                        
-                        // Check if there are more segments after this one
-                        if let Some((segment, note)) = text_manager.preview_next_segment() {
-                            if !segment.is_empty() {
-                                println!("Next segment preview:");
-                                println!("{}", segment);
-                                if let Some(note_text) = note {
-                                    println!("[Note: {}]", note_text);
-                                }
-                                println!("---");
-                            }
-                        } else {
-                            // No more segments after this one, quit immediately
-                            info!("All segments processed. Exiting...");
+                        None => {
+                            info!("No segments found. Exiting...");
                             show_donation_prompt();
                             std::process::exit(0);
                         }
-                    } else {
-                        // No segments available at all, quit the application
-                        info!("No segments found. Exiting...");
-                        show_donation_prompt();
-                        std::process::exit(0);
+                    },
+                    Action::Previous => {
+                        if let Some((segment, _)) = text_manager.previous() {
+                            deliver_segment(
+                                &segment,
+                                &clipboard,
+                                clipboard_type,
+                                config.paste.unwrap_or(true),
+                                config.paste_mode,
+                                config.typeout_delay_ms,
+                            );
+                        }
+                        print_preview(text_manager.peek());
+                    }
+                    Action::Reset => {
+                        text_manager.reset();
+                        println!("Reset to the first segment.");
+                        print_preview(text_manager.peek());
+                    }
+                    Action::Skip => {
+                        if text_manager.skip() {
+                            println!("Skipped the current segment.");
+                        }
+                        print_preview(text_manager.peek());
                     }
                 }
             }
         })?;
 
         // This code will only run if the event loop exits
-        self.hotkey_manager.lock().unwrap().unregister(hotkey)?;
-        info!("Unregistered hotkey");
+        let mut manager = self.hotkey_manager.lock().unwrap();
+        for (hotkey, _) in &hotkeys {
+            manager.unregister(*hotkey)?;
+        }
+        info!("Unregistered hotkeys");
 
         Ok(())
     }
@@ -409,8 +662,11 @@ fn load_config(args: &Args) -> Result<Config> {
     let mut config = if let Some(config_path) = &args.config {
         let config_content = fs::read_to_string(config_path)
             .with_context(|| format!("Failed to read config file: {}", config_path.display()))?;
-        toml::from_str(&config_content)
-            .with_context(|| format!("Failed to parse config file: {}", config_path.display()))?
+        let config: Config = toml::from_str(&config_content)
+            .with_context(|| format!("Failed to parse config file: {}", config_path.display()))?;
+        hotkey_config::validate_hotkeys(&config, &config_content)
+            .with_context(|| format!("Invalid hotkey in config file: {}", config_path.display()))?;
+        config
     } else {
         Config::default()
     };
@@ -421,20 +677,32 @@ fn load_config(args: &Args) -> Result<Config> {
         config.delimiter = args.delimiter.clone();
     }
     config.paste = Some(!args.no_paste);
+    if args.clipboard_backend.is_some() {
+        config.clipboard_backend = args.clipboard_backend.clone();
+    }
+    if args.selection {
+        config.selection = true;
+    }
+    if let Some(paste_mode) = args.paste_mode {
+        config.paste_mode = paste_mode;
+    }
+    if let Some(typeout_delay_ms) = args.typeout_delay_ms {
+        config.typeout_delay_ms = typeout_delay_ms;
+    }
 
     Ok(config)
 }
 
 fn main() -> Result<()> {
     let args = Args::parse();
-    
+
     // Configure logging level based on verbose flag
     let log_level = if args.verbose {
         tracing::Level::INFO
     } else {
         tracing::Level::WARN
     };
-    
+
     tracing_subscriber::fmt()
         .with_max_level(log_level)
         .init();
@@ -451,4 +719,4 @@ fn main() -> Result<()> {
     let app = EasypasteApp::new(config.clone())?;
 
     app.run(config)
-}
\ No newline at end of file
+}